@@ -0,0 +1,25 @@
+// This file is part of Tangle.
+// Copyright (C) 2022-2024 Webb Technologies Inc.
+//
+// Tangle is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Tangle is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Tangle.  If not, see <http://www.gnu.org/licenses/>.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Crypto primitives shared between Tangle's job-verification pallets and their offchain
+//! counterparts (the key type under which role/DKG keys are kept in the keystore).
+
+use sp_core::crypto::KeyTypeId;
+
+/// The `KeyTypeId` under which role keys (DKG participant keys, signing keys, etc.) are stored
+/// in the node's keystore.
+pub const ROLE_KEY_TYPE: KeyTypeId = KeyTypeId(*b"role");
@@ -0,0 +1,106 @@
+// This file is part of Tangle.
+// Copyright (C) 2022-2024 Webb Technologies Inc.
+//
+// Tangle is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Tangle is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Tangle.  If not, see <http://www.gnu.org/licenses/>.
+
+//! BIP340 (Schnorr over secp256k1) and BIP341 (Taproot key tweaking) verification, mirroring the
+//! tweak/normalization rules `frost-secp256k1-tr` applies when a DKG produces a Bitcoin-spendable
+//! Taproot output key.
+
+use k256::{
+	elliptic_curve::{group::GroupEncoding, ops::Reduce, sec1::ToEncodedPoint, PrimeField},
+	AffinePoint, ProjectivePoint, Scalar, U256,
+};
+use sp_core::hashing::sha2_256;
+use sp_std::vec::Vec;
+
+/// Computes the BIP340 tagged hash `SHA256(SHA256(tag) || SHA256(tag) || msg)`.
+fn tagged_hash(tag: &[u8], chunks: &[&[u8]]) -> [u8; 32] {
+	let tag_hash = sha2_256(tag);
+	let mut engine = Vec::with_capacity(64 + chunks.iter().map(|c| c.len()).sum::<usize>());
+	engine.extend_from_slice(&tag_hash);
+	engine.extend_from_slice(&tag_hash);
+	for chunk in chunks {
+		engine.extend_from_slice(chunk);
+	}
+	sha2_256(&engine)
+}
+
+/// Lifts a 32-byte x-coordinate to the even-Y point on the curve with that x-coordinate, per
+/// BIP340's "lift_x". Returns `None` if no such point exists.
+fn lift_x(x: &[u8; 32]) -> Option<AffinePoint> {
+	let mut encoded = [0u8; 33];
+	encoded[0] = 0x02;
+	encoded[1..].copy_from_slice(x);
+	Option::from(AffinePoint::from_bytes(
+		k256::CompressedPoint::from_slice(&encoded).ok()?,
+	))
+}
+
+/// Returns the x-only (32-byte) encoding of `point`, which BIP340 always takes to have even Y.
+fn x_only(point: &AffinePoint) -> [u8; 32] {
+	let encoded = point.to_encoded_point(false);
+	let mut out = [0u8; 32];
+	out.copy_from_slice(encoded.x().expect("affine point is never the identity"));
+	out
+}
+
+/// Negates `point` if its Y coordinate is odd, per BIP340's normalization rule.
+fn normalize_even_y(point: ProjectivePoint) -> ProjectivePoint {
+	let affine = point.to_affine();
+	if bool::from(affine.to_encoded_point(false).y().expect("not identity")[31] & 1 != 0) {
+		-point
+	} else {
+		point
+	}
+}
+
+/// Computes the BIP341 TapTweak scalar `t = H_TapTweak(P || merkle_root)` for internal key `p`.
+pub(crate) fn tap_tweak(internal_key: &[u8; 32], merkle_root: Option<&[u8]>) -> Scalar {
+	let hash = match merkle_root {
+		Some(root) => tagged_hash(b"TapTweak", &[internal_key, root]),
+		None => tagged_hash(b"TapTweak", &[internal_key]),
+	};
+	Scalar::reduce(U256::from_be_slice(&hash))
+}
+
+/// Tweaks the x-only internal key `p` by `merkle_root`, returning the x-only output key `Q`.
+///
+/// Returns `None` if `p` does not correspond to a valid curve point.
+pub fn tweak_pubkey(internal_key: &[u8; 32], merkle_root: Option<&[u8]>) -> Option<[u8; 32]> {
+	let p = lift_x(internal_key)?;
+	let t = tap_tweak(internal_key, merkle_root);
+	let q = normalize_even_y(ProjectivePoint::from(p) + ProjectivePoint::GENERATOR * t);
+	Some(x_only(&q.to_affine()))
+}
+
+/// Verifies a 64-byte BIP340 signature `(r, s)` over `msg` against x-only public key `pubkey`,
+/// per `s·G = R + H_BIP340(r || pubkey || msg)·Q` with both `R` and `Q` normalized to even Y.
+pub fn verify(pubkey: &[u8; 32], msg: &[u8], sig: &[u8; 64]) -> bool {
+	let Some(q) = lift_x(pubkey) else { return false };
+
+	let r_bytes: [u8; 32] = sig[..32].try_into().expect("slice is 32 bytes");
+	let Some(r) = lift_x(&r_bytes) else { return false };
+
+	// BIP340 requires rejecting a signature outright if `s >= n` (the curve order) rather than
+	// silently reducing it mod `n` — `from_repr` only succeeds for the canonical representative of
+	// each scalar, so a non-canonical `s` fails verification here instead of being coerced into one.
+	let s_bytes: [u8; 32] = sig[32..].try_into().expect("slice is 32 bytes");
+	let Some(s) = Option::<Scalar>::from(Scalar::from_repr(s_bytes.into())) else { return false };
+	let e_hash = tagged_hash(b"BIP0340/challenge", &[&r_bytes, pubkey, msg]);
+	let e = Scalar::reduce(U256::from_be_slice(&e_hash));
+
+	let expected = ProjectivePoint::GENERATOR * s - ProjectivePoint::from(q) * e;
+	normalize_even_y(ProjectivePoint::from(r)) == expected
+}
@@ -0,0 +1,550 @@
+// This file is part of Tangle.
+// Copyright (C) 2022-2024 Webb Technologies Inc.
+//
+// Tangle is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Tangle is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Tangle.  If not, see <http://www.gnu.org/licenses/>.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! # DKG Pallet
+//!
+//! Verifies job results produced off-chain by Tangle's distributed key generation protocol
+//! before the runtime accepts them: group keys and participant attestations from key-generation
+//! rounds (`JobResult::DKGPhaseOne`), signatures produced by the resulting group key
+//! (`JobResult::DKGPhaseTwo`), and key-refresh/resharing transitions attested to by a threshold
+//! of the previous round's participants (`JobResult::DKGPhaseThree`). Every group key accepted
+//! from a `DKGPhaseOne` round is also appended to an on-chain [`transparency`] log, giving an
+//! auditable, tamper-evident history of every key the committee has generated.
+
+mod bip340;
+mod transparency;
+mod types;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+pub use pallet::*;
+pub use types::FeeInfo;
+
+use frame_support::pallet_prelude::*;
+use frame_system::pallet_prelude::BlockNumberFor;
+use parity_scale_codec::Encode;
+use sp_core::{ecdsa, ed25519, keccak_256, sr25519};
+use sp_std::vec::Vec;
+use tangle_primitives::jobs::{
+	DKGRefreshResult, DKGResult, DKGSignatureResult, DkgKeyType, JobResult,
+};
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>>
+			+ IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// The balance type used to denominate job fees.
+		type Balance: Parameter + Member + Default + Copy + MaxEncodedLen;
+		/// The origin allowed to update the fee schedule.
+		type UpdateOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// The fee schedule charged for DKG jobs.
+	#[pallet::storage]
+	#[pallet::getter(fn fee_info)]
+	pub type FeeInfo<T: Config> = StorageValue<_, types::FeeInfo<T::Balance>, ValueQuery>;
+
+	/// The current root of the transparency log's Merkle tree.
+	#[pallet::storage]
+	#[pallet::getter(fn transparency_root)]
+	pub type TransparencyRoot<T> = StorageValue<_, [u8; 32], ValueQuery, TransparencyEmptyRoot>;
+
+	#[pallet::type_value]
+	pub fn TransparencyEmptyRoot() -> [u8; 32] {
+		transparency::zero_hashes()[transparency::DEPTH as usize]
+	}
+
+	/// The number of group keys appended to the transparency log so far.
+	#[pallet::storage]
+	#[pallet::getter(fn transparency_leaf_count)]
+	pub type TransparencyLeafCount<T> = StorageValue<_, u64, ValueQuery>;
+
+	/// The incremental Merkle tree's "filled subtree" frontier: the most recent node at each
+	/// level that is still waiting for a sibling. Lets a new leaf be appended in `O(DEPTH)`
+	/// rather than replaying the whole log.
+	#[pallet::storage]
+	pub type TransparencyFilledSubtrees<T> = StorageValue<
+		_,
+		[[u8; 32]; transparency::DEPTH as usize],
+		ValueQuery,
+		TransparencyEmptyFilledSubtrees,
+	>;
+
+	#[pallet::type_value]
+	pub fn TransparencyEmptyFilledSubtrees() -> [[u8; 32]; transparency::DEPTH as usize] {
+		let zeros = transparency::zero_hashes();
+		let mut subtrees = [[0u8; 32]; transparency::DEPTH as usize];
+		subtrees.copy_from_slice(&zeros[..transparency::DEPTH as usize]);
+		subtrees
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The fee schedule was updated.
+		FeeUpdated { fee: types::FeeInfo<T::Balance> },
+		/// A verified `DKGPhaseOne` group key was appended to the transparency log.
+		DkgKeyLogged { leaf_index: u64, root: [u8; 32] },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// A job result was submitted with no participants listed.
+		NoParticipantsFound,
+		/// A job result was submitted with no signatures attached.
+		NoSignaturesFound,
+		/// Fewer distinct, valid participant signatures were found than `threshold` requires.
+		NotEnoughSigners,
+		/// The signature at this index into `signatures` duplicated an earlier one, did not
+		/// verify against any remaining participant, or attested to a key other than the agreed
+		/// group key. Carried so misbehaviour can be attributed to a specific submission for
+		/// slashing, rather than aborting the whole job anonymously.
+		InvalidSignatureFrom(u16),
+		/// A `DKGPhaseTwo` signature recovered to a key other than the claimed `signing_key`.
+		SigningKeyMismatch,
+		/// A signature did not verify against the key it was claimed to be produced by.
+		InvalidSignature,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Updates the fee schedule charged for DKG jobs. Restricted to [`Config::UpdateOrigin`].
+		#[pallet::call_index(0)]
+		#[pallet::weight(10_000)]
+		pub fn set_fee(
+			origin: OriginFor<T>,
+			new_fee: types::FeeInfo<T::Balance>,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+
+			FeeInfo::<T>::put(new_fee.clone());
+			Self::deposit_event(Event::FeeUpdated { fee: new_fee });
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Verifies a DKG job result, returning an error if it does not meet the protocol's
+	/// threshold and signature requirements.
+	pub fn verify(result: JobResult) -> DispatchResult {
+		match result {
+			JobResult::DKGPhaseOne(info) => Self::verify_key(info),
+			JobResult::DKGPhaseTwo(info) => Self::verify_signature(info),
+			JobResult::DKGPhaseThree(info) => Self::verify_refresh(info),
+		}
+	}
+
+	fn verify_key(info: DKGResult) -> DispatchResult {
+		ensure!(!info.participants.is_empty(), Error::<T>::NoParticipantsFound);
+		ensure!(!info.signatures.is_empty(), Error::<T>::NoSignaturesFound);
+
+		match info.key_type {
+			DkgKeyType::Ecdsa => Self::verify_key_ecdsa(&info)?,
+			DkgKeyType::Schnorr => Self::verify_key_schnorr(&info)?,
+			DkgKeyType::Bip340 => Self::verify_key_bip340(&info)?,
+			DkgKeyType::Ed25519 => Self::verify_key_ed25519(&info)?,
+		}
+
+		Self::log_verified_key(&info.key_type, &info.key);
+		Ok(())
+	}
+
+	/// Appends a freshly verified `DKGPhaseOne` group key to the transparency log, updating the
+	/// root and emitting the new root and leaf index.
+	fn log_verified_key(key_type: &DkgKeyType, key: &[u8]) {
+		let block_number = frame_system::Pallet::<T>::block_number();
+		let leaf = transparency::leaf_hash(key_type, key, block_number);
+
+		let leaf_index = TransparencyLeafCount::<T>::get();
+		let mut filled_subtrees = TransparencyFilledSubtrees::<T>::get();
+		let root = transparency::insert(&mut filled_subtrees, leaf_index, leaf);
+
+		TransparencyFilledSubtrees::<T>::put(filled_subtrees);
+		TransparencyLeafCount::<T>::put(leaf_index + 1);
+		TransparencyRoot::<T>::put(root);
+		Self::deposit_event(Event::DkgKeyLogged { leaf_index, root });
+	}
+
+	/// Confirms that `key` (of the given `key_type`, logged at `block_number`) is the leaf at
+	/// `leaf_index` under `root`, given its audit path in `proof`. Lets external verifiers confirm
+	/// a key was ratified by this pallet's transparency log without trusting a single RPC node.
+	pub fn verify_inclusion(
+		key_type: &DkgKeyType,
+		key: &[u8],
+		block_number: BlockNumberFor<T>,
+		leaf_index: u64,
+		proof: &[[u8; 32]],
+		root: [u8; 32],
+	) -> bool {
+		let leaf = transparency::leaf_hash(key_type, key, block_number);
+		transparency::verify_inclusion(leaf, leaf_index, proof, root)
+	}
+
+	fn verify_signature(info: DKGSignatureResult) -> DispatchResult {
+		match info.key_type {
+			DkgKeyType::Ecdsa => Self::verify_signature_ecdsa(&info),
+			DkgKeyType::Schnorr => Self::verify_signature_schnorr(&info),
+			DkgKeyType::Bip340 => Self::verify_signature_bip340(&info),
+			DkgKeyType::Ed25519 => Self::verify_signature_ed25519(&info),
+		}
+	}
+
+	fn verify_refresh(info: DKGRefreshResult) -> DispatchResult {
+		ensure!(!info.previous_participants.is_empty(), Error::<T>::NoParticipantsFound);
+		ensure!(!info.signatures.is_empty(), Error::<T>::NoSignaturesFound);
+
+		// Every transition signature must commit to this exact `(previous_key, new_key)` pair,
+		// so a signer can never be credited towards the threshold for attesting to some other
+		// proposed successor key. Each field is hashed separately before being concatenated:
+		// `previous_key`/`new_key` are attacker-supplied and variable length, so concatenating
+		// the raw bytes directly would let a signature over `(X, Y)` be resubmitted as `(X', Y')`
+		// for any split with `X' ++ Y' == X ++ Y`. Hashing first fixes each field's contribution
+		// at 32 bytes, so there is no other boundary left to re-split at.
+		let transition = [keccak_256(&info.previous_key), keccak_256(&info.new_key)].concat();
+
+		match info.key_type {
+			DkgKeyType::Ecdsa => {
+				let hash = keccak_256(&transition);
+				Self::match_signers_ecdsa(
+					&info.previous_participants,
+					&info.signatures,
+					&hash,
+					info.threshold,
+				)
+			},
+			DkgKeyType::Schnorr => {
+				let hash = keccak_256(&transition.encode());
+				Self::match_signers_schnorr(
+					&info.previous_participants,
+					&info.signatures,
+					&hash,
+					info.threshold,
+				)
+			},
+			DkgKeyType::Ed25519 => {
+				let hash = keccak_256(&transition);
+				Self::match_signers_ed25519(
+					&info.previous_participants,
+					&info.signatures,
+					&hash,
+					info.threshold,
+				)
+			},
+			DkgKeyType::Bip340 => Self::match_signers_bip340(
+				&info.previous_participants,
+				&info.signatures,
+				&transition,
+				info.threshold,
+			),
+		}
+	}
+
+	fn verify_key_ecdsa(info: &DKGResult) -> DispatchResult {
+		let hash = keccak_256(&info.key);
+		Self::match_signers_ecdsa(&info.participants, &info.signatures, &hash, info.threshold)
+	}
+
+	fn match_signers_ecdsa(
+		participants: &[Vec<u8>],
+		signatures: &[Vec<u8>],
+		hash: &[u8; 32],
+		threshold: u16,
+	) -> DispatchResult {
+		let mut claimed: Vec<&Vec<u8>> = Vec::new();
+		let mut seen_signatures: Vec<&Vec<u8>> = Vec::new();
+
+		for (index, sig_bytes) in signatures.iter().enumerate() {
+			let index = index as u16;
+			ensure!(!seen_signatures.contains(&sig_bytes), Error::<T>::InvalidSignatureFrom(index));
+			seen_signatures.push(sig_bytes);
+
+			let mut matched = false;
+			if let Some(signature) = ecdsa::Signature::from_slice(sig_bytes) {
+				for participant in participants {
+					if claimed.contains(&participant) {
+						continue;
+					}
+					let Ok(public) = ecdsa::Public::try_from(participant.as_slice()) else {
+						continue
+					};
+					if sp_io::crypto::ecdsa_verify_prehashed(&signature, hash, &public) {
+						claimed.push(participant);
+						matched = true;
+						break;
+					}
+				}
+			}
+			ensure!(matched, Error::<T>::InvalidSignatureFrom(index));
+		}
+
+		ensure!(claimed.len() as u16 >= threshold, Error::<T>::NotEnoughSigners);
+		Ok(())
+	}
+
+	fn verify_key_ed25519(info: &DKGResult) -> DispatchResult {
+		let hash = keccak_256(&info.key);
+		Self::match_signers_ed25519(&info.participants, &info.signatures, &hash, info.threshold)
+	}
+
+	fn match_signers_ed25519(
+		participants: &[Vec<u8>],
+		signatures: &[Vec<u8>],
+		hash: &[u8; 32],
+		threshold: u16,
+	) -> DispatchResult {
+		let mut claimed: Vec<&Vec<u8>> = Vec::new();
+		let mut seen_signatures: Vec<&Vec<u8>> = Vec::new();
+
+		for (index, sig_bytes) in signatures.iter().enumerate() {
+			let index = index as u16;
+			ensure!(!seen_signatures.contains(&sig_bytes), Error::<T>::InvalidSignatureFrom(index));
+			seen_signatures.push(sig_bytes);
+
+			let mut matched = false;
+			if let Some(signature) = ed25519::Signature::from_slice(sig_bytes) {
+				for participant in participants {
+					if claimed.contains(&participant) {
+						continue;
+					}
+					let Ok(public) = ed25519::Public::try_from(participant.as_slice()) else {
+						continue
+					};
+					if sp_io::crypto::ed25519_verify(&signature, hash, &public) {
+						claimed.push(participant);
+						matched = true;
+						break;
+					}
+				}
+			}
+			ensure!(matched, Error::<T>::InvalidSignatureFrom(index));
+		}
+
+		ensure!(claimed.len() as u16 >= threshold, Error::<T>::NotEnoughSigners);
+		Ok(())
+	}
+
+	fn verify_key_schnorr(info: &DKGResult) -> DispatchResult {
+		let hash = keccak_256(&info.key.encode());
+
+		// Fast path: DKG rounds overwhelmingly submit `signatures[i]` as participant `i`'s
+		// attestation, so try verifying the whole batch as one multiscalar-multiplication check
+		// before falling back to the exhaustive per-signature match below. `δ_i` is sampled
+		// fresh per call by the host batching context, never reused and never fixed to `1` —
+		// reusing or fixing it would let an attacker craft cancelling forgeries across the batch.
+		if Self::batch_verify_schnorr_aligned(&info.participants, &info.signatures, &hash) {
+			let mut seen_signatures: Vec<&Vec<u8>> = Vec::new();
+			let mut seen_participants: Vec<&Vec<u8>> = Vec::new();
+			for (index, (participant, sig_bytes)) in
+				info.participants.iter().zip(&info.signatures).enumerate()
+			{
+				let index = index as u16;
+				ensure!(!seen_signatures.contains(&sig_bytes), Error::<T>::InvalidSignatureFrom(index));
+				seen_signatures.push(sig_bytes);
+
+				// sr25519 signing is randomized, so two distinct-looking valid signatures can
+				// still come from the same signer — dedup by participant, not just by signature
+				// bytes, or a single key could be double-counted towards `threshold`.
+				ensure!(
+					!seen_participants.contains(&participant),
+					Error::<T>::InvalidSignatureFrom(index)
+				);
+				seen_participants.push(participant);
+			}
+			ensure!(
+				info.participants.len() as u16 >= info.threshold,
+				Error::<T>::NotEnoughSigners
+			);
+			return Ok(());
+		}
+
+		Self::match_signers_schnorr(&info.participants, &info.signatures, &hash, info.threshold)
+	}
+
+	/// Attempts to verify `signatures[i]` against `participants[i]` for every index in a single
+	/// batch, using the runtime's random-coefficient batch-verification host functions. Returns
+	/// `false` (without reporting which pair failed) if the lengths differ or any pair is
+	/// invalid; callers must fall back to [`Self::match_signers_schnorr`] for a granular result.
+	fn batch_verify_schnorr_aligned(
+		participants: &[Vec<u8>],
+		signatures: &[Vec<u8>],
+		hash: &[u8; 32],
+	) -> bool {
+		if participants.is_empty() || participants.len() != signatures.len() {
+			return false;
+		}
+
+		sp_io::crypto::start_batch_verify();
+		for (participant, sig_bytes) in participants.iter().zip(signatures) {
+			let (Ok(public), Some(signature)) = (
+				sr25519::Public::try_from(participant.as_slice()),
+				sr25519::Signature::from_slice(sig_bytes),
+			) else {
+				// `finish_batch_verify` must be called to clear the batching context even when
+				// we already know the result.
+				sp_io::crypto::finish_batch_verify();
+				return false;
+			};
+			sp_io::crypto::sr25519_verify(&signature, hash, &public);
+		}
+		sp_io::crypto::finish_batch_verify()
+	}
+
+	fn match_signers_schnorr(
+		participants: &[Vec<u8>],
+		signatures: &[Vec<u8>],
+		hash: &[u8; 32],
+		threshold: u16,
+	) -> DispatchResult {
+		let mut claimed: Vec<&Vec<u8>> = Vec::new();
+		let mut seen_signatures: Vec<&Vec<u8>> = Vec::new();
+
+		for (index, sig_bytes) in signatures.iter().enumerate() {
+			let index = index as u16;
+			ensure!(!seen_signatures.contains(&sig_bytes), Error::<T>::InvalidSignatureFrom(index));
+			seen_signatures.push(sig_bytes);
+
+			let mut matched = false;
+			if let Some(signature) = sr25519::Signature::from_slice(sig_bytes) {
+				for participant in participants {
+					if claimed.contains(&participant) {
+						continue;
+					}
+					let Ok(public) = sr25519::Public::try_from(participant.as_slice()) else {
+						continue
+					};
+					if sp_io::crypto::sr25519_verify(&signature, hash, &public) {
+						claimed.push(participant);
+						matched = true;
+						break;
+					}
+				}
+			}
+			ensure!(matched, Error::<T>::InvalidSignatureFrom(index));
+		}
+
+		ensure!(claimed.len() as u16 >= threshold, Error::<T>::NotEnoughSigners);
+		Ok(())
+	}
+
+	fn verify_key_bip340(info: &DKGResult) -> DispatchResult {
+		// `info.key` is the untweaked internal key `P`; participants attest to the Taproot
+		// output key `Q = P + H_TapTweak(P || merkle_root)·G` that the group will actually sign
+		// with, so that is what their signatures must verify against.
+		let internal_key: [u8; 32] =
+			info.key.as_slice().try_into().map_err(|_| Error::<T>::InvalidSignature)?;
+		let output_key = bip340::tweak_pubkey(&internal_key, info.merkle_root.as_deref())
+			.ok_or(Error::<T>::InvalidSignature)?;
+
+		Self::match_signers_bip340(&info.participants, &info.signatures, &output_key, info.threshold)
+	}
+
+	fn match_signers_bip340(
+		participants: &[Vec<u8>],
+		signatures: &[Vec<u8>],
+		msg: &[u8],
+		threshold: u16,
+	) -> DispatchResult {
+		let mut claimed: Vec<&Vec<u8>> = Vec::new();
+		let mut seen_signatures: Vec<&Vec<u8>> = Vec::new();
+
+		for (index, sig_bytes) in signatures.iter().enumerate() {
+			let index = index as u16;
+			ensure!(!seen_signatures.contains(&sig_bytes), Error::<T>::InvalidSignatureFrom(index));
+			seen_signatures.push(sig_bytes);
+
+			let mut matched = false;
+			if let Ok(sig) = <[u8; 64]>::try_from(sig_bytes.as_slice()) {
+				for participant in participants {
+					if claimed.contains(&participant) {
+						continue;
+					}
+					let Ok(pubkey) = <[u8; 32]>::try_from(participant.as_slice()) else { continue };
+					if bip340::verify(&pubkey, msg, &sig) {
+						claimed.push(participant);
+						matched = true;
+						break;
+					}
+				}
+			}
+			ensure!(matched, Error::<T>::InvalidSignatureFrom(index));
+		}
+
+		ensure!(claimed.len() as u16 >= threshold, Error::<T>::NotEnoughSigners);
+		Ok(())
+	}
+
+	fn verify_signature_bip340(info: &DKGSignatureResult) -> DispatchResult {
+		let internal_key: [u8; 32] =
+			info.signing_key.as_slice().try_into().map_err(|_| Error::<T>::InvalidSignature)?;
+		let output_key = bip340::tweak_pubkey(&internal_key, info.merkle_root.as_deref())
+			.ok_or(Error::<T>::InvalidSignature)?;
+		let signature: [u8; 64] =
+			info.signature.as_slice().try_into().map_err(|_| Error::<T>::InvalidSignature)?;
+
+		ensure!(bip340::verify(&output_key, &info.data, &signature), Error::<T>::InvalidSignature);
+		Ok(())
+	}
+
+	fn verify_signature_ecdsa(info: &DKGSignatureResult) -> DispatchResult {
+		let hash = keccak_256(&info.data);
+		let signature =
+			ecdsa::Signature::from_slice(&info.signature).ok_or(Error::<T>::InvalidSignature)?;
+
+		// ECDSA signatures in this scheme carry a recovery id, so the claimed `signing_key` is
+		// checked against the key recovery actually yields rather than via direct verification.
+		let recovered = sp_io::crypto::secp256k1_ecdsa_recover_compressed(&signature.0, &hash)
+			.map_err(|_| Error::<T>::InvalidSignature)?;
+		ensure!(recovered.as_slice() == info.signing_key.as_slice(), Error::<T>::SigningKeyMismatch);
+		Ok(())
+	}
+
+	fn verify_signature_ed25519(info: &DKGSignatureResult) -> DispatchResult {
+		let hash = keccak_256(&info.data);
+		let signature =
+			ed25519::Signature::from_slice(&info.signature).ok_or(Error::<T>::InvalidSignature)?;
+		let public = ed25519::Public::try_from(info.signing_key.as_slice())
+			.map_err(|_| Error::<T>::InvalidSignature)?;
+
+		ensure!(sp_io::crypto::ed25519_verify(&signature, &hash, &public), Error::<T>::InvalidSignature);
+		Ok(())
+	}
+
+	fn verify_signature_schnorr(info: &DKGSignatureResult) -> DispatchResult {
+		let hash = keccak_256(&info.data.encode());
+		let signature =
+			sr25519::Signature::from_slice(&info.signature).ok_or(Error::<T>::InvalidSignature)?;
+		let public = sr25519::Public::try_from(info.signing_key.as_slice())
+			.map_err(|_| Error::<T>::InvalidSignature)?;
+
+		ensure!(sp_io::crypto::sr25519_verify(&signature, &hash, &public), Error::<T>::InvalidSignature);
+		Ok(())
+	}
+}
@@ -13,12 +13,18 @@
 //
 // You should have received a copy of the GNU General Public License
 // along with Tangle.  If not, see <http://www.gnu.org/licenses/>.
-use crate::{mock::*, types::FeeInfo, Error, FeeInfo as FeeInfoStorage};
+use crate::{bip340, mock::*, transparency, types::FeeInfo, Error, FeeInfo as FeeInfoStorage};
 use frame_support::{assert_noop, assert_ok, error::BadOrigin};
+use k256::{elliptic_curve::rand_core::OsRng, schnorr::signature::Signer};
 use parity_scale_codec::Encode;
-use sp_core::{crypto::ByteArray, ecdsa, keccak_256, sr25519};
-use sp_io::crypto::{ecdsa_generate, ecdsa_sign_prehashed, sr25519_generate, sr25519_sign};
-use tangle_primitives::jobs::{DKGResult, DKGSignatureResult, DkgKeyType, JobResult};
+use sp_core::{crypto::ByteArray, ecdsa, ed25519, keccak_256, sr25519};
+use sp_io::crypto::{
+	ecdsa_generate, ecdsa_sign_prehashed, ed25519_generate, ed25519_sign, sr25519_generate,
+	sr25519_sign,
+};
+use tangle_primitives::jobs::{
+	DKGRefreshResult, DKGResult, DKGSignatureResult, DkgKeyType, JobResult,
+};
 
 fn mock_pub_key_ecdsa() -> ecdsa::Public {
 	ecdsa_generate(tangle_crypto_primitives::ROLE_KEY_TYPE, None)
@@ -28,6 +34,10 @@ fn mock_pub_key_sr25519() -> sr25519::Public {
 	sr25519_generate(tangle_crypto_primitives::ROLE_KEY_TYPE, None)
 }
 
+fn mock_pub_key_ed25519() -> ed25519::Public {
+	ed25519_generate(tangle_crypto_primitives::ROLE_KEY_TYPE, None)
+}
+
 fn mock_signature_ecdsa(pub_key: ecdsa::Public, role_key: ecdsa::Public) -> Vec<u8> {
 	let msg = role_key.encode();
 	let hash = keccak_256(&msg);
@@ -46,6 +56,66 @@ fn mock_signature_sr25519(pub_key: sr25519::Public, role_key: sr25519::Public) -
 	signature.encode()
 }
 
+fn mock_signature_ed25519(pub_key: ed25519::Public, role_key: ed25519::Public) -> Vec<u8> {
+	let msg = role_key.encode();
+	let hash = keccak_256(&msg);
+	let signature: ed25519::Signature =
+		ed25519_sign(tangle_crypto_primitives::ROLE_KEY_TYPE, &pub_key, &hash).unwrap();
+	signature.encode()
+}
+
+/// Signs the raw bytes `msg` directly, for key-refresh transitions where the message isn't a
+/// single encoded public key but a `(previous_key, new_key)` pair.
+fn mock_signature_ecdsa_over(pub_key: ecdsa::Public, msg: &[u8]) -> Vec<u8> {
+	let hash = keccak_256(msg);
+	let signature: ecdsa::Signature =
+		ecdsa_sign_prehashed(tangle_crypto_primitives::ROLE_KEY_TYPE, &pub_key, &hash).unwrap();
+	signature.encode()
+}
+
+/// Signs `msg` SCALE-encoded as a byte vector, for key-refresh transitions where the message
+/// isn't a single encoded public key but a `(previous_key, new_key)` pair.
+fn mock_signature_sr25519_over(pub_key: sr25519::Public, msg: &[u8]) -> Vec<u8> {
+	let hash = keccak_256(&msg.to_vec().encode());
+	let signature: sr25519::Signature =
+		sr25519_sign(tangle_crypto_primitives::ROLE_KEY_TYPE, &pub_key, &hash).unwrap();
+	signature.encode()
+}
+
+/// Signs the raw bytes `msg` directly, for key-refresh transitions where the message isn't a
+/// single encoded public key but a `(previous_key, new_key)` pair.
+fn mock_signature_ed25519_over(pub_key: ed25519::Public, msg: &[u8]) -> Vec<u8> {
+	let hash = keccak_256(msg);
+	let signature: ed25519::Signature =
+		ed25519_sign(tangle_crypto_primitives::ROLE_KEY_TYPE, &pub_key, &hash).unwrap();
+	signature.encode()
+}
+
+fn mock_keypair_bip340() -> (k256::schnorr::SigningKey, [u8; 32]) {
+	let signing_key = k256::schnorr::SigningKey::random(&mut OsRng);
+	let pub_key = signing_key.verifying_key().to_bytes().into();
+	(signing_key, pub_key)
+}
+
+fn mock_signature_bip340(signing_key: &k256::schnorr::SigningKey, msg: &[u8]) -> Vec<u8> {
+	let signature: k256::schnorr::Signature = signing_key.sign(msg);
+	signature.to_bytes().to_vec()
+}
+
+/// Signs `msg` with the *tweaked* secret corresponding to `internal_key`, i.e. what the group
+/// would actually sign with once its Taproot output key has been derived.
+fn mock_signature_bip340_tweaked(
+	internal_key: &k256::schnorr::SigningKey,
+	merkle_root: Option<&[u8]>,
+	msg: &[u8],
+) -> Vec<u8> {
+	let internal_key_bytes: [u8; 32] = internal_key.verifying_key().to_bytes().into();
+	let tweak = bip340::tap_tweak(&internal_key_bytes, merkle_root);
+	let tweaked_scalar = internal_key.as_nonzero_scalar().as_ref() + &tweak;
+	let tweaked_key = k256::schnorr::SigningKey::from_bytes(&tweaked_scalar.to_bytes()).unwrap();
+	mock_signature_bip340(&tweaked_key, msg)
+}
+
 #[test]
 fn set_fees_works() {
 	new_test_ext().execute_with(|| {
@@ -71,6 +141,7 @@ fn dkg_key_verifcation_works_for_ecdsa() {
 	new_test_ext().execute_with(|| {
 		let job_to_verify = DKGResult {
 			key_type: DkgKeyType::Ecdsa,
+			merkle_root: None,
 			key: vec![],
 			participants: vec![],
 			signatures: vec![],
@@ -85,6 +156,7 @@ fn dkg_key_verifcation_works_for_ecdsa() {
 
 		let job_to_verify = DKGResult {
 			key_type: DkgKeyType::Ecdsa,
+			merkle_root: None,
 			key: vec![],
 			participants: vec![mock_pub_key_ecdsa().as_mut().to_vec()],
 			signatures: vec![],
@@ -103,30 +175,32 @@ fn dkg_key_verifcation_works_for_ecdsa() {
 
 		let job_to_verify = DKGResult {
 			key_type: DkgKeyType::Ecdsa,
+			merkle_root: None,
 			key: vec![],
 			participants: vec![mock_pub_key_ecdsa().as_mut().to_vec()],
 			signatures: vec![signature.clone()],
 			threshold: 1,
 		};
 
-		// should fail for less than threshold
+		// should identify the offending signature when it matches no listed participant
 		assert_noop!(
 			DKG::verify(JobResult::DKGPhaseOne(job_to_verify)),
-			Error::<Runtime>::NotEnoughSigners
+			Error::<Runtime>::InvalidSignatureFrom(0)
 		);
 
 		let job_to_verify = DKGResult {
 			key_type: DkgKeyType::Ecdsa,
+			merkle_root: None,
 			key: pub_key.0.to_vec(),
 			participants: vec![pub_key.as_mut().to_vec()],
 			signatures: vec![signature.clone(), signature.clone()],
 			threshold: 1,
 		};
 
-		// should fail for duplicate signers
+		// should identify the offending signature when it duplicates an earlier one
 		assert_noop!(
 			DKG::verify(JobResult::DKGPhaseOne(job_to_verify)),
-			Error::<Runtime>::DuplicateSignature
+			Error::<Runtime>::InvalidSignatureFrom(1)
 		);
 
 		// works correctly when all params as expected
@@ -136,6 +210,7 @@ fn dkg_key_verifcation_works_for_ecdsa() {
 		let signature_two = mock_signature_ecdsa(participant_two, participant_one);
 		let job_to_verify = DKGResult {
 			key_type: DkgKeyType::Ecdsa,
+			merkle_root: None,
 			key: participant_one.to_raw_vec(),
 			participants: vec![
 				participant_one.as_mut().to_vec(),
@@ -155,6 +230,7 @@ fn dkg_key_verifcation_works_for_schnorr() {
 	new_test_ext().execute_with(|| {
 		let job_to_verify = DKGResult {
 			key_type: DkgKeyType::Schnorr,
+			merkle_root: None,
 			key: mock_pub_key_sr25519().to_vec(),
 			participants: vec![],
 			signatures: vec![],
@@ -169,6 +245,7 @@ fn dkg_key_verifcation_works_for_schnorr() {
 
 		let job_to_verify = DKGResult {
 			key_type: DkgKeyType::Schnorr,
+			merkle_root: None,
 			key: vec![],
 			participants: vec![mock_pub_key_sr25519().as_mut().to_vec()],
 			signatures: vec![],
@@ -187,30 +264,32 @@ fn dkg_key_verifcation_works_for_schnorr() {
 
 		let job_to_verify = DKGResult {
 			key_type: DkgKeyType::Schnorr,
+			merkle_root: None,
 			key: pub_key.to_vec(),
 			participants: vec![mock_pub_key_sr25519().as_mut().to_vec()],
 			signatures: vec![signature.clone()],
 			threshold: 1,
 		};
 
-		// should fail for less than threshold
+		// should identify the offending signature when it matches no listed participant
 		assert_noop!(
 			DKG::verify(JobResult::DKGPhaseOne(job_to_verify)),
-			Error::<Runtime>::NotEnoughSigners
+			Error::<Runtime>::InvalidSignatureFrom(0)
 		);
 
 		let job_to_verify = DKGResult {
 			key_type: DkgKeyType::Schnorr,
+			merkle_root: None,
 			key: pub_key.to_vec(),
 			participants: vec![pub_key.as_mut().to_vec()],
 			signatures: vec![signature.clone(), signature.clone()],
 			threshold: 1,
 		};
 
-		// should fail for duplicate signers
+		// should identify the offending signature when it duplicates an earlier one
 		assert_noop!(
 			DKG::verify(JobResult::DKGPhaseOne(job_to_verify)),
-			Error::<Runtime>::DuplicateSignature
+			Error::<Runtime>::InvalidSignatureFrom(1)
 		);
 
 		// works correctly when all params as expected
@@ -220,6 +299,7 @@ fn dkg_key_verifcation_works_for_schnorr() {
 		let signature_two = mock_signature_sr25519(participant_two, participant_one);
 		let job_to_verify = DKGResult {
 			key_type: DkgKeyType::Schnorr,
+			merkle_root: None,
 			key: participant_one.to_raw_vec(),
 			participants: vec![
 				participant_one.as_mut().to_vec(),
@@ -234,6 +314,50 @@ fn dkg_key_verifcation_works_for_schnorr() {
 	});
 }
 
+#[test]
+fn dkg_key_verifcation_works_for_schnorr_batch_path() {
+	new_test_ext().execute_with(|| {
+		// `participants[i]`/`signatures[i]` aligned, so this takes the batch-verify fast path
+		// rather than falling back to `match_signers_schnorr`.
+		let mut participant_one = mock_pub_key_sr25519();
+		let mut participant_two = mock_pub_key_sr25519();
+		let signature_one = mock_signature_sr25519(participant_one, participant_one);
+		let signature_two = mock_signature_sr25519(participant_two, participant_one);
+		let job_to_verify = DKGResult {
+			key_type: DkgKeyType::Schnorr,
+			merkle_root: None,
+			key: participant_one.to_raw_vec(),
+			participants: vec![
+				participant_one.as_mut().to_vec(),
+				participant_two.as_mut().to_vec(),
+			],
+			signatures: vec![signature_one, signature_two],
+			threshold: 2,
+		};
+		assert_ok!(DKG::verify(JobResult::DKGPhaseOne(job_to_verify)));
+
+		// sr25519 signing is randomized, so the same signer can produce two distinct-looking
+		// valid signatures over the same hash — a duplicated participant must not be credited
+		// towards `threshold` twice even though the batch check itself still passes.
+		let mut participant = mock_pub_key_sr25519();
+		let signature_a = mock_signature_sr25519(participant, participant);
+		let signature_b = mock_signature_sr25519(participant, participant);
+		assert_ne!(signature_a, signature_b);
+		let job_to_verify = DKGResult {
+			key_type: DkgKeyType::Schnorr,
+			merkle_root: None,
+			key: participant.to_raw_vec(),
+			participants: vec![participant.as_mut().to_vec(), participant.as_mut().to_vec()],
+			signatures: vec![signature_a, signature_b],
+			threshold: 2,
+		};
+		assert_noop!(
+			DKG::verify(JobResult::DKGPhaseOne(job_to_verify)),
+			Error::<Runtime>::InvalidSignatureFrom(1)
+		);
+	});
+}
+
 #[test]
 fn dkg_signature_verifcation_works_ecdsa() {
 	new_test_ext().execute_with(|| {
@@ -243,6 +367,7 @@ fn dkg_signature_verifcation_works_ecdsa() {
 
 		let job_to_verify: DKGSignatureResult = DKGSignatureResult {
 			key_type: DkgKeyType::Ecdsa,
+			merkle_root: None,
 			signature,
 			data: pub_key.to_raw_vec(),
 			signing_key: pub_key.to_raw_vec(),
@@ -257,6 +382,7 @@ fn dkg_signature_verifcation_works_ecdsa() {
 		let signature = mock_signature_ecdsa(pub_key, pub_key);
 		let job_to_verify: DKGSignatureResult = DKGSignatureResult {
 			key_type: DkgKeyType::Ecdsa,
+			merkle_root: None,
 			signature,
 			data: pub_key.to_raw_vec(),
 			signing_key: pub_key.to_raw_vec(),
@@ -276,6 +402,7 @@ fn dkg_signature_verifcation_works_schnorr() {
 
 		let job_to_verify: DKGSignatureResult = DKGSignatureResult {
 			key_type: DkgKeyType::Schnorr,
+			merkle_root: None,
 			signature,
 			data: pub_key.to_raw_vec(),
 			signing_key: pub_key.to_raw_vec(),
@@ -290,6 +417,7 @@ fn dkg_signature_verifcation_works_schnorr() {
 		let signature = mock_signature_sr25519(pub_key, pub_key);
 		let job_to_verify: DKGSignatureResult = DKGSignatureResult {
 			key_type: DkgKeyType::Schnorr,
+			merkle_root: None,
 			signature,
 			data: pub_key.to_raw_vec(),
 			signing_key: pub_key.to_raw_vec(),
@@ -299,3 +427,472 @@ fn dkg_signature_verifcation_works_schnorr() {
 		assert_ok!(DKG::verify(JobResult::DKGPhaseTwo(job_to_verify)));
 	});
 }
+
+#[test]
+fn dkg_key_verifcation_works_for_bip340() {
+	new_test_ext().execute_with(|| {
+		let job_to_verify = DKGResult {
+			key_type: DkgKeyType::Bip340,
+			merkle_root: None,
+			key: vec![],
+			participants: vec![],
+			signatures: vec![],
+			threshold: 2,
+		};
+
+		// should fail for empty participants
+		assert_noop!(
+			DKG::verify(JobResult::DKGPhaseOne(job_to_verify)),
+			Error::<Runtime>::NoParticipantsFound
+		);
+
+		let (_internal_key, internal_key_bytes) = mock_keypair_bip340();
+		let output_key = bip340::tweak_pubkey(&internal_key_bytes, None).unwrap();
+
+		// works correctly: both participants attest to the tweaked Taproot output key, not the
+		// untweaked internal key
+		let (participant_one, participant_one_bytes) = mock_keypair_bip340();
+		let (participant_two, participant_two_bytes) = mock_keypair_bip340();
+		let signature_one = mock_signature_bip340(&participant_one, &output_key);
+		let signature_two = mock_signature_bip340(&participant_two, &output_key);
+
+		let job_to_verify = DKGResult {
+			key_type: DkgKeyType::Bip340,
+			merkle_root: None,
+			key: internal_key_bytes.to_vec(),
+			participants: vec![participant_one_bytes.to_vec(), participant_two_bytes.to_vec()],
+			signatures: vec![signature_two, signature_one],
+			threshold: 2,
+		};
+		assert_ok!(DKG::verify(JobResult::DKGPhaseOne(job_to_verify)));
+	});
+}
+
+#[test]
+fn dkg_signature_verifcation_works_bip340() {
+	new_test_ext().execute_with(|| {
+		let (internal_key, internal_key_bytes) = mock_keypair_bip340();
+		let data = b"tangle taproot payout".to_vec();
+
+		// a signature from the untweaked internal key does not verify against the tweaked
+		// Taproot output key
+		let bad_signature = mock_signature_bip340(&internal_key, &data);
+		let job_to_verify = DKGSignatureResult {
+			key_type: DkgKeyType::Bip340,
+			merkle_root: None,
+			signature: bad_signature,
+			data: data.clone(),
+			signing_key: internal_key_bytes.to_vec(),
+		};
+		assert_noop!(
+			DKG::verify(JobResult::DKGPhaseTwo(job_to_verify)),
+			Error::<Runtime>::InvalidSignature
+		);
+
+		// a signature produced with the tweaked secret verifies once `DKG::verify` tweaks the
+		// claimed internal `signing_key` the same way
+		let signature = mock_signature_bip340_tweaked(&internal_key, None, &data);
+		let job_to_verify = DKGSignatureResult {
+			key_type: DkgKeyType::Bip340,
+			merkle_root: None,
+			signature,
+			data,
+			signing_key: internal_key_bytes.to_vec(),
+		};
+		assert_ok!(DKG::verify(JobResult::DKGPhaseTwo(job_to_verify)));
+	});
+}
+
+#[test]
+fn dkg_key_verifcation_works_for_ed25519() {
+	new_test_ext().execute_with(|| {
+		let job_to_verify = DKGResult {
+			key_type: DkgKeyType::Ed25519,
+			merkle_root: None,
+			key: vec![],
+			participants: vec![],
+			signatures: vec![],
+			threshold: 2,
+		};
+
+		// should fail for empty participants
+		assert_noop!(
+			DKG::verify(JobResult::DKGPhaseOne(job_to_verify)),
+			Error::<Runtime>::NoParticipantsFound
+		);
+
+		let job_to_verify = DKGResult {
+			key_type: DkgKeyType::Ed25519,
+			merkle_root: None,
+			key: vec![],
+			participants: vec![mock_pub_key_ed25519().as_mut().to_vec()],
+			signatures: vec![],
+			threshold: 2,
+		};
+
+		// should fail for empty keys/signatures
+		assert_noop!(
+			DKG::verify(JobResult::DKGPhaseOne(job_to_verify)),
+			Error::<Runtime>::NoSignaturesFound
+		);
+
+		// setup key/signature
+		let mut pub_key = mock_pub_key_ed25519();
+		let signature = mock_signature_ed25519(pub_key, pub_key);
+
+		let job_to_verify = DKGResult {
+			key_type: DkgKeyType::Ed25519,
+			merkle_root: None,
+			key: vec![],
+			participants: vec![mock_pub_key_ed25519().as_mut().to_vec()],
+			signatures: vec![signature.clone()],
+			threshold: 1,
+		};
+
+		// should identify the offending signature when it matches no listed participant
+		assert_noop!(
+			DKG::verify(JobResult::DKGPhaseOne(job_to_verify)),
+			Error::<Runtime>::InvalidSignatureFrom(0)
+		);
+
+		let job_to_verify = DKGResult {
+			key_type: DkgKeyType::Ed25519,
+			merkle_root: None,
+			key: pub_key.to_raw_vec(),
+			participants: vec![pub_key.as_mut().to_vec()],
+			signatures: vec![signature.clone(), signature.clone()],
+			threshold: 1,
+		};
+
+		// should identify the offending signature when it duplicates an earlier one
+		assert_noop!(
+			DKG::verify(JobResult::DKGPhaseOne(job_to_verify)),
+			Error::<Runtime>::InvalidSignatureFrom(1)
+		);
+
+		// works correctly when all params as expected
+		let mut participant_one = mock_pub_key_ed25519();
+		let mut participant_two = mock_pub_key_ed25519();
+		let signature_one = mock_signature_ed25519(participant_one, participant_one);
+		let signature_two = mock_signature_ed25519(participant_two, participant_one);
+		let job_to_verify = DKGResult {
+			key_type: DkgKeyType::Ed25519,
+			merkle_root: None,
+			key: participant_one.to_raw_vec(),
+			participants: vec![
+				participant_one.as_mut().to_vec(),
+				participant_two.as_mut().to_vec(),
+			],
+			signatures: vec![signature_two, signature_one],
+			threshold: 1,
+		};
+
+		assert_ok!(DKG::verify(JobResult::DKGPhaseOne(job_to_verify)),);
+	});
+}
+
+#[test]
+fn dkg_signature_verifcation_works_ed25519() {
+	new_test_ext().execute_with(|| {
+		// setup key/signature
+		let pub_key = mock_pub_key_ed25519();
+		let signature = mock_signature_ed25519(pub_key, mock_pub_key_ed25519());
+
+		let job_to_verify: DKGSignatureResult = DKGSignatureResult {
+			key_type: DkgKeyType::Ed25519,
+			merkle_root: None,
+			signature,
+			data: pub_key.to_raw_vec(),
+			signing_key: pub_key.to_raw_vec(),
+		};
+
+		// should fail for invalid keys
+		assert_noop!(
+			DKG::verify(JobResult::DKGPhaseTwo(job_to_verify)),
+			Error::<Runtime>::InvalidSignature
+		);
+
+		let signature = mock_signature_ed25519(pub_key, pub_key);
+		let job_to_verify: DKGSignatureResult = DKGSignatureResult {
+			key_type: DkgKeyType::Ed25519,
+			merkle_root: None,
+			signature,
+			data: pub_key.to_raw_vec(),
+			signing_key: pub_key.to_raw_vec(),
+		};
+
+		// should work with correct params
+		assert_ok!(DKG::verify(JobResult::DKGPhaseTwo(job_to_verify)));
+	});
+}
+
+#[test]
+fn dkg_refresh_verifcation_works_for_ecdsa() {
+	new_test_ext().execute_with(|| {
+		let job_to_verify = DKGRefreshResult {
+			key_type: DkgKeyType::Ecdsa,
+			previous_key: vec![],
+			new_key: vec![],
+			previous_participants: vec![],
+			signatures: vec![],
+			threshold: 2,
+		};
+
+		// should fail for empty participants
+		assert_noop!(
+			DKG::verify(JobResult::DKGPhaseThree(job_to_verify)),
+			Error::<Runtime>::NoParticipantsFound
+		);
+
+		let job_to_verify = DKGRefreshResult {
+			key_type: DkgKeyType::Ecdsa,
+			previous_key: vec![],
+			new_key: vec![],
+			previous_participants: vec![mock_pub_key_ecdsa().as_mut().to_vec()],
+			signatures: vec![],
+			threshold: 2,
+		};
+
+		// should fail for empty signatures
+		assert_noop!(
+			DKG::verify(JobResult::DKGPhaseThree(job_to_verify)),
+			Error::<Runtime>::NoSignaturesFound
+		);
+
+		let previous_key = mock_pub_key_ecdsa().to_raw_vec();
+		let new_key = mock_pub_key_ecdsa().to_raw_vec();
+		let mut signer = mock_pub_key_ecdsa();
+
+		// a signature attesting to a different new key than the one being submitted must not be
+		// credited towards the threshold
+		let mismatched_transition =
+			[keccak_256(&previous_key), keccak_256(&mock_pub_key_ecdsa().to_raw_vec())].concat();
+		let mismatched_signature = mock_signature_ecdsa_over(signer, &mismatched_transition);
+		let job_to_verify = DKGRefreshResult {
+			key_type: DkgKeyType::Ecdsa,
+			previous_key: previous_key.clone(),
+			new_key: new_key.clone(),
+			previous_participants: vec![signer.as_mut().to_vec()],
+			signatures: vec![mismatched_signature],
+			threshold: 1,
+		};
+		assert_noop!(
+			DKG::verify(JobResult::DKGPhaseThree(job_to_verify)),
+			Error::<Runtime>::InvalidSignatureFrom(0)
+		);
+
+		// works correctly when a threshold of the previous participants attest to exactly this
+		// (previous_key, new_key) transition
+		let transition = [keccak_256(&previous_key), keccak_256(&new_key)].concat();
+		let mut participant_one = mock_pub_key_ecdsa();
+		let mut participant_two = mock_pub_key_ecdsa();
+		let signature_one = mock_signature_ecdsa_over(participant_one, &transition);
+		let signature_two = mock_signature_ecdsa_over(participant_two, &transition);
+		let job_to_verify = DKGRefreshResult {
+			key_type: DkgKeyType::Ecdsa,
+			previous_key,
+			new_key,
+			previous_participants: vec![
+				participant_one.as_mut().to_vec(),
+				participant_two.as_mut().to_vec(),
+			],
+			signatures: vec![signature_two, signature_one],
+			threshold: 2,
+		};
+		assert_ok!(DKG::verify(JobResult::DKGPhaseThree(job_to_verify)));
+	});
+}
+
+#[test]
+fn dkg_transparency_log_records_verified_keys() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		let mut participant_one = mock_pub_key_ecdsa();
+		let mut participant_two = mock_pub_key_ecdsa();
+		let signature_one = mock_signature_ecdsa(participant_one, participant_one);
+		let signature_two = mock_signature_ecdsa(participant_two, participant_one);
+		let key = participant_one.to_raw_vec();
+		let job_to_verify = DKGResult {
+			key_type: DkgKeyType::Ecdsa,
+			merkle_root: None,
+			key: key.clone(),
+			participants: vec![
+				participant_one.as_mut().to_vec(),
+				participant_two.as_mut().to_vec(),
+			],
+			signatures: vec![signature_two, signature_one],
+			threshold: 1,
+		};
+
+		// the log starts out empty
+		assert_eq!(DKG::transparency_leaf_count(), 0);
+
+		assert_ok!(DKG::verify(JobResult::DKGPhaseOne(job_to_verify)));
+
+		// the verified key was appended as the log's first leaf
+		assert_eq!(DKG::transparency_leaf_count(), 1);
+		let root = DKG::transparency_root();
+
+		// the log's only leaf sits at index 0, so every sibling along its audit path is an empty
+		// subtree
+		let proof: Vec<[u8; 32]> =
+			(0..transparency::DEPTH).map(transparency::empty_subtree_hash).collect();
+
+		assert!(DKG::verify_inclusion(&DkgKeyType::Ecdsa, &key, 1, 0, &proof, root));
+
+		// a proof must fail to verify against a root, key, key type, block number, or leaf index
+		// it wasn't produced for
+		assert!(!DKG::verify_inclusion(&DkgKeyType::Ecdsa, &key, 1, 0, &proof, [0u8; 32]));
+		assert!(!DKG::verify_inclusion(
+			&DkgKeyType::Ecdsa,
+			&participant_two.to_raw_vec(),
+			1,
+			0,
+			&proof,
+			root
+		));
+		assert!(!DKG::verify_inclusion(&DkgKeyType::Schnorr, &key, 1, 0, &proof, root));
+		assert!(!DKG::verify_inclusion(&DkgKeyType::Ecdsa, &key, 2, 0, &proof, root));
+		assert!(!DKG::verify_inclusion(&DkgKeyType::Ecdsa, &key, 1, 1, &proof, root));
+	});
+}
+
+#[test]
+fn dkg_refresh_verifcation_works_for_schnorr() {
+	new_test_ext().execute_with(|| {
+		let previous_key = mock_pub_key_sr25519().to_raw_vec();
+		let new_key = mock_pub_key_sr25519().to_raw_vec();
+		let mut signer = mock_pub_key_sr25519();
+
+		// a signature attesting to a different new key than the one being submitted must not be
+		// credited towards the threshold
+		let mismatched_transition =
+			[keccak_256(&previous_key), keccak_256(&mock_pub_key_sr25519().to_raw_vec())].concat();
+		let mismatched_signature = mock_signature_sr25519_over(signer, &mismatched_transition);
+		let job_to_verify = DKGRefreshResult {
+			key_type: DkgKeyType::Schnorr,
+			previous_key: previous_key.clone(),
+			new_key: new_key.clone(),
+			previous_participants: vec![signer.as_mut().to_vec()],
+			signatures: vec![mismatched_signature],
+			threshold: 1,
+		};
+		assert_noop!(
+			DKG::verify(JobResult::DKGPhaseThree(job_to_verify)),
+			Error::<Runtime>::InvalidSignatureFrom(0)
+		);
+
+		// works correctly when a threshold of the previous participants attest to exactly this
+		// (previous_key, new_key) transition
+		let transition = [keccak_256(&previous_key), keccak_256(&new_key)].concat();
+		let mut participant_one = mock_pub_key_sr25519();
+		let mut participant_two = mock_pub_key_sr25519();
+		let signature_one = mock_signature_sr25519_over(participant_one, &transition);
+		let signature_two = mock_signature_sr25519_over(participant_two, &transition);
+		let job_to_verify = DKGRefreshResult {
+			key_type: DkgKeyType::Schnorr,
+			previous_key,
+			new_key,
+			previous_participants: vec![
+				participant_one.as_mut().to_vec(),
+				participant_two.as_mut().to_vec(),
+			],
+			signatures: vec![signature_two, signature_one],
+			threshold: 2,
+		};
+		assert_ok!(DKG::verify(JobResult::DKGPhaseThree(job_to_verify)));
+	});
+}
+
+#[test]
+fn dkg_refresh_verifcation_works_for_ed25519() {
+	new_test_ext().execute_with(|| {
+		let previous_key = mock_pub_key_ed25519().to_raw_vec();
+		let new_key = mock_pub_key_ed25519().to_raw_vec();
+		let mut signer = mock_pub_key_ed25519();
+
+		// a signature attesting to a different new key than the one being submitted must not be
+		// credited towards the threshold
+		let mismatched_transition =
+			[keccak_256(&previous_key), keccak_256(&mock_pub_key_ed25519().to_raw_vec())].concat();
+		let mismatched_signature = mock_signature_ed25519_over(signer, &mismatched_transition);
+		let job_to_verify = DKGRefreshResult {
+			key_type: DkgKeyType::Ed25519,
+			previous_key: previous_key.clone(),
+			new_key: new_key.clone(),
+			previous_participants: vec![signer.as_mut().to_vec()],
+			signatures: vec![mismatched_signature],
+			threshold: 1,
+		};
+		assert_noop!(
+			DKG::verify(JobResult::DKGPhaseThree(job_to_verify)),
+			Error::<Runtime>::InvalidSignatureFrom(0)
+		);
+
+		// works correctly when a threshold of the previous participants attest to exactly this
+		// (previous_key, new_key) transition
+		let transition = [keccak_256(&previous_key), keccak_256(&new_key)].concat();
+		let mut participant_one = mock_pub_key_ed25519();
+		let mut participant_two = mock_pub_key_ed25519();
+		let signature_one = mock_signature_ed25519_over(participant_one, &transition);
+		let signature_two = mock_signature_ed25519_over(participant_two, &transition);
+		let job_to_verify = DKGRefreshResult {
+			key_type: DkgKeyType::Ed25519,
+			previous_key,
+			new_key,
+			previous_participants: vec![
+				participant_one.as_mut().to_vec(),
+				participant_two.as_mut().to_vec(),
+			],
+			signatures: vec![signature_two, signature_one],
+			threshold: 2,
+		};
+		assert_ok!(DKG::verify(JobResult::DKGPhaseThree(job_to_verify)));
+	});
+}
+
+#[test]
+fn dkg_refresh_verifcation_works_for_bip340() {
+	new_test_ext().execute_with(|| {
+		let (_, previous_key) = mock_keypair_bip340();
+		let (_, new_key) = mock_keypair_bip340();
+		let (signer_key, signer) = mock_keypair_bip340();
+
+		// a signature attesting to a different new key than the one being submitted must not be
+		// credited towards the threshold
+		let (_, other_new_key) = mock_keypair_bip340();
+		let mismatched_transition =
+			[keccak_256(&previous_key), keccak_256(&other_new_key)].concat();
+		let mismatched_signature = mock_signature_bip340(&signer_key, &mismatched_transition);
+		let job_to_verify = DKGRefreshResult {
+			key_type: DkgKeyType::Bip340,
+			previous_key: previous_key.to_vec(),
+			new_key: new_key.to_vec(),
+			previous_participants: vec![signer.to_vec()],
+			signatures: vec![mismatched_signature],
+			threshold: 1,
+		};
+		assert_noop!(
+			DKG::verify(JobResult::DKGPhaseThree(job_to_verify)),
+			Error::<Runtime>::InvalidSignatureFrom(0)
+		);
+
+		// works correctly when a threshold of the previous participants attest to exactly this
+		// (previous_key, new_key) transition
+		let transition = [keccak_256(&previous_key), keccak_256(&new_key)].concat();
+		let (participant_one_key, participant_one) = mock_keypair_bip340();
+		let (participant_two_key, participant_two) = mock_keypair_bip340();
+		let signature_one = mock_signature_bip340(&participant_one_key, &transition);
+		let signature_two = mock_signature_bip340(&participant_two_key, &transition);
+		let job_to_verify = DKGRefreshResult {
+			key_type: DkgKeyType::Bip340,
+			previous_key: previous_key.to_vec(),
+			new_key: new_key.to_vec(),
+			previous_participants: vec![participant_one.to_vec(), participant_two.to_vec()],
+			signatures: vec![signature_two, signature_one],
+			threshold: 2,
+		};
+		assert_ok!(DKG::verify(JobResult::DKGPhaseThree(job_to_verify)));
+	});
+}
@@ -0,0 +1,141 @@
+// This file is part of Tangle.
+// Copyright (C) 2022-2024 Webb Technologies Inc.
+//
+// Tangle is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Tangle is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Tangle.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A certificate-transparency-style append-only log of verified DKG group keys.
+//!
+//! Every group key the pallet accepts from a `DKGPhaseOne` round is hashed into a leaf and
+//! appended to a fixed-depth incremental Merkle tree, following the standard "filled subtrees"
+//! construction: appending a leaf only touches `O(DEPTH)` storage, rather than replaying the
+//! whole log, and [`verify_inclusion`] lets anyone holding a leaf's audit path confirm it was
+//! ratified by this pallet without trusting whichever RPC node served them the log. Hashing is
+//! domain separated (a `0x00` prefix for leaves, `0x01` for internal nodes, per RFC 6962) so a
+//! leaf hash can never double as an internal node hash or vice versa.
+
+use parity_scale_codec::Encode;
+use sp_core::hashing::blake2_256;
+use sp_std::vec::Vec;
+use tangle_primitives::jobs::DkgKeyType;
+
+/// Depth of the transparency log's Merkle tree. `2^32` leaves is far beyond any plausible number
+/// of DKG rounds the chain will ever run.
+pub(crate) const DEPTH: u32 = 32;
+
+const LEAF_PREFIX: u8 = 0x00;
+const NODE_PREFIX: u8 = 0x01;
+
+/// Hashes two sibling nodes into their parent, per RFC 6962's domain-separated internal node hash.
+pub(crate) fn node_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+	let mut engine = Vec::with_capacity(1 + 64);
+	engine.push(NODE_PREFIX);
+	engine.extend_from_slice(left);
+	engine.extend_from_slice(right);
+	blake2_256(&engine)
+}
+
+/// Hashes a verified DKG group key into its transparency-log leaf value:
+/// `hash(0x00 ‖ key_type ‖ group_key ‖ block_number)`.
+pub(crate) fn leaf_hash<BlockNumber: Encode>(
+	key_type: &DkgKeyType,
+	key: &[u8],
+	block_number: BlockNumber,
+) -> [u8; 32] {
+	let mut engine = Vec::new();
+	engine.push(LEAF_PREFIX);
+	engine.extend_from_slice(&key_type.encode());
+	engine.extend_from_slice(key);
+	engine.extend_from_slice(&block_number.encode());
+	blake2_256(&engine)
+}
+
+/// The hash of an empty subtree rooted at `level` (`0` is an empty leaf): `ZEROS[0] =
+/// hash(0x00)`, `ZEROS[i] = node_hash(ZEROS[i - 1], ZEROS[i - 1])`.
+///
+/// Recomputes the chain from scratch, so this is `O(level)` — fine for a one-off lookup, but use
+/// [`zero_hashes`] instead of calling this in a loop, or the loop becomes `O(DEPTH^2)`.
+pub(crate) fn empty_subtree_hash(level: u32) -> [u8; 32] {
+	let mut hash = blake2_256(&[LEAF_PREFIX]);
+	for _ in 0..level {
+		hash = node_hash(&hash, &hash);
+	}
+	hash
+}
+
+/// Precomputes `empty_subtree_hash(level)` for every `level` in `0..=DEPTH` in a single `O(DEPTH)`
+/// pass, so callers that need more than one level's empty-subtree hash (like [`insert`]) can index
+/// into this table instead of paying `O(level)` per lookup.
+pub(crate) fn zero_hashes() -> [[u8; 32]; DEPTH as usize + 1] {
+	let mut zeros = [[0u8; 32]; DEPTH as usize + 1];
+	zeros[0] = blake2_256(&[LEAF_PREFIX]);
+	for level in 1..=DEPTH as usize {
+		zeros[level] = node_hash(&zeros[level - 1], &zeros[level - 1]);
+	}
+	zeros
+}
+
+/// Appends `leaf` to the incremental Merkle tree described by `filled_subtrees` (the frontier
+/// node at each level) and `leaf_index` (the number of leaves appended so far), returning the new
+/// root and updating `filled_subtrees` in place.
+///
+/// This is the standard incremental-tree append algorithm: at each level, an even-indexed node
+/// becomes part of the frontier (it still needs a sibling), while an odd-indexed node combines
+/// with the frontier entry left behind by its even sibling.
+pub(crate) fn insert(
+	filled_subtrees: &mut [[u8; 32]; DEPTH as usize],
+	leaf_index: u64,
+	leaf: [u8; 32],
+) -> [u8; 32] {
+	let zeros = zero_hashes();
+	let mut current_index = leaf_index;
+	let mut current_hash = leaf;
+
+	for (level, subtree) in filled_subtrees.iter_mut().enumerate() {
+		if current_index % 2 == 0 {
+			*subtree = current_hash;
+			current_hash = node_hash(&current_hash, &zeros[level]);
+		} else {
+			current_hash = node_hash(subtree, &current_hash);
+		}
+		current_index /= 2;
+	}
+
+	current_hash
+}
+
+/// Confirms that `leaf` sits at `leaf_index` under `root`, given the sibling hash at each level of
+/// its audit path in `proof`.
+pub(crate) fn verify_inclusion(
+	leaf: [u8; 32],
+	leaf_index: u64,
+	proof: &[[u8; 32]],
+	root: [u8; 32],
+) -> bool {
+	if proof.len() != DEPTH as usize {
+		return false
+	}
+
+	let mut current_index = leaf_index;
+	let mut current_hash = leaf;
+	for sibling in proof {
+		current_hash = if current_index % 2 == 0 {
+			node_hash(&current_hash, sibling)
+		} else {
+			node_hash(sibling, &current_hash)
+		};
+		current_index /= 2;
+	}
+
+	current_hash == root
+}
@@ -0,0 +1,30 @@
+// This file is part of Tangle.
+// Copyright (C) 2022-2024 Webb Technologies Inc.
+//
+// Tangle is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Tangle is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Tangle.  If not, see <http://www.gnu.org/licenses/>.
+use parity_scale_codec::{Decode, Encode};
+use scale_info::TypeInfo;
+
+/// The fee schedule charged for the jobs `pallet-dkg` verifies.
+#[derive(Encode, Decode, TypeInfo, Clone, Debug, Default, PartialEq, Eq)]
+pub struct FeeInfo<Balance> {
+	/// Flat fee charged for every job, regardless of type.
+	pub base_fee: Balance,
+	/// Additional fee charged per participant for a key-generation (`DKGPhaseOne`) job.
+	pub dkg_validator_fee: Balance,
+	/// Additional fee charged per participant for a signing (`DKGPhaseTwo`) job.
+	pub sig_validator_fee: Balance,
+	/// Additional fee charged per participant for a key-refresh job.
+	pub refresh_validator_fee: Balance,
+}
@@ -0,0 +1,106 @@
+// This file is part of Tangle.
+// Copyright (C) 2022-2024 Webb Technologies Inc.
+//
+// Tangle is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Tangle is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Tangle.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Types describing the results of jobs that the Tangle job-management pallets hand off to
+//! `pallet-dkg` (and friends) for on-chain verification.
+
+use parity_scale_codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_std::vec::Vec;
+
+/// The cryptographic scheme used to produce a DKG group key / signature.
+#[derive(Encode, Decode, TypeInfo, Clone, Debug, PartialEq, Eq)]
+pub enum DkgKeyType {
+	/// secp256k1 ECDSA.
+	Ecdsa,
+	/// sr25519 Schnorr.
+	Schnorr,
+	/// BIP340/BIP341 Taproot-tweaked secp256k1 Schnorr.
+	Bip340,
+	/// ed25519.
+	Ed25519,
+}
+
+/// The outcome of a DKG key-generation round (`DKGPhaseOne`): the freshly generated group key,
+/// the set of participants that took part, and each participant's attestation signature over the
+/// group key.
+#[derive(Encode, Decode, TypeInfo, Clone, Debug, PartialEq, Eq)]
+pub struct DKGResult {
+	/// The scheme the group key and signatures were produced under.
+	pub key_type: DkgKeyType,
+	/// The BIP341 Merkle tree root to tweak `key` with before verification, for
+	/// `DkgKeyType::Bip340` group keys that commit to a script tree. `None` for a key-only
+	/// (script-path-less) Taproot output, and unused for all other key types.
+	pub merkle_root: Option<Vec<u8>>,
+	/// The generated group public key.
+	pub key: Vec<u8>,
+	/// The public keys of the participants that took part in the round.
+	pub participants: Vec<Vec<u8>>,
+	/// Each participant's signature attesting to `key`.
+	pub signatures: Vec<Vec<u8>>,
+	/// The minimum number of distinct, valid participant signatures required for `key` to be
+	/// accepted.
+	pub threshold: u16,
+}
+
+/// The outcome of a threshold-signing round (`DKGPhaseTwo`): a single signature produced by the
+/// group key over some data.
+#[derive(Encode, Decode, TypeInfo, Clone, Debug, PartialEq, Eq)]
+pub struct DKGSignatureResult {
+	/// The scheme `signing_key` and `signature` were produced under.
+	pub key_type: DkgKeyType,
+	/// The BIP341 Merkle tree root `signing_key` was tweaked with, for `DkgKeyType::Bip340`
+	/// signatures. `None` for a key-only Taproot output, and unused for all other key types.
+	pub merkle_root: Option<Vec<u8>>,
+	/// The produced signature.
+	pub signature: Vec<u8>,
+	/// The data that was signed.
+	pub data: Vec<u8>,
+	/// The key that `signature` is claimed to have been produced by.
+	pub signing_key: Vec<u8>,
+}
+
+/// The outcome of a key-refresh/resharing round (`DKGPhaseThree`): a new group key, attested to
+/// by a threshold of the *previous* round's participants as a valid successor to the previous
+/// group key.
+#[derive(Encode, Decode, TypeInfo, Clone, Debug, PartialEq, Eq)]
+pub struct DKGRefreshResult {
+	/// The scheme `previous_key`, `new_key`, and the transition signatures were produced under.
+	pub key_type: DkgKeyType,
+	/// The group key being replaced.
+	pub previous_key: Vec<u8>,
+	/// The newly generated group key.
+	pub new_key: Vec<u8>,
+	/// The public keys of the participants that held a share of `previous_key`.
+	pub previous_participants: Vec<Vec<u8>>,
+	/// Each previous participant's signature attesting to the `(previous_key, new_key)`
+	/// transition.
+	pub signatures: Vec<Vec<u8>>,
+	/// The minimum number of distinct, valid previous-participant signatures required for the
+	/// transition to be accepted.
+	pub threshold: u16,
+}
+
+/// The result of a job submitted to Tangle's job-management system.
+#[derive(Encode, Decode, TypeInfo, Clone, Debug, PartialEq, Eq)]
+pub enum JobResult {
+	/// A DKG key-generation round.
+	DKGPhaseOne(DKGResult),
+	/// A DKG threshold-signing round.
+	DKGPhaseTwo(DKGSignatureResult),
+	/// A DKG key-refresh/resharing round.
+	DKGPhaseThree(DKGRefreshResult),
+}